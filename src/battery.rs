@@ -0,0 +1,103 @@
+#[cfg(target_os = "espidf")]
+use esp_idf_svc::hal::{
+    adc::attenuation::DB_11,
+    adc::oneshot::config::{AdcChannelConfig, Calibration},
+    adc::oneshot::{AdcChannelDriver, AdcDriver},
+    gpio::ADCPin,
+    peripheral::Peripheral,
+};
+
+/// Typical single cell LiPo discharge curve as (millivolts, percent), highest voltage first.
+pub const LIPO_CURVE: &[(u16, u8)] = &[
+    (4200, 100),
+    (4100, 90),
+    (4000, 80),
+    (3900, 65),
+    (3800, 50),
+    (3700, 30),
+    (3600, 15),
+    (3500, 5),
+    (3300, 0),
+];
+
+/// Battery voltage measured through a resistor divider on an ADC pin.
+#[cfg(target_os = "espidf")]
+pub struct Battery<'d, T: ADCPin> {
+    channel: AdcChannelDriver<'d, T, AdcDriver<'d, T::Adc>>,
+    divider_ratio: f32,
+}
+
+#[cfg(target_os = "espidf")]
+impl<'d, T: ADCPin> Battery<'d, T> {
+    /// The divider ratio depends on the wiring, for example 2.0 when the battery
+    /// is connected through two equal resistors. With 11 dB attenuation the pin
+    /// itself can measure up to about 3100 mV.
+    pub fn new(
+        adc: impl Peripheral<P = T::Adc> + 'd,
+        pin: impl Peripheral<P = T> + 'd,
+        divider_ratio: f32,
+    ) -> anyhow::Result<Self> {
+        let config = AdcChannelConfig {
+            attenuation: DB_11,
+            calibration: Calibration::Curve,
+            ..Default::default()
+        };
+        let channel = AdcChannelDriver::new(AdcDriver::new(adc)?, pin, &config)?;
+
+        Ok(Self {
+            channel,
+            divider_ratio,
+        })
+    }
+
+    /// Battery voltage in millivolts, taking the divider into account.
+    pub fn read_voltage_mv(&mut self) -> anyhow::Result<u16> {
+        let pin_mv = self.channel.read()?;
+        Ok((pin_mv as f32 * self.divider_ratio).round() as u16)
+    }
+}
+
+/// Charge level of a LiPo battery in percent.
+pub fn percent(voltage_mv: u16) -> u8 {
+    percent_with_curve(voltage_mv, LIPO_CURVE)
+}
+
+/// Charge level in percent, interpolated linearly between the points of the given curve.
+///
+/// The curve must be ordered from the highest voltage to the lowest, like `LIPO_CURVE`.
+pub fn percent_with_curve(voltage_mv: u16, curve: &[(u16, u8)]) -> u8 {
+    let Some(&(full_mv, full_percent)) = curve.first() else {
+        return 0;
+    };
+    if voltage_mv >= full_mv {
+        return full_percent;
+    }
+
+    for points in curve.windows(2) {
+        let (upper_mv, upper_percent) = points[0];
+        let (lower_mv, lower_percent) = points[1];
+        if voltage_mv >= lower_mv {
+            let span = u32::from(upper_mv - lower_mv).max(1);
+            let offset = u32::from(voltage_mv - lower_mv);
+            let gain = u32::from(upper_percent.saturating_sub(lower_percent));
+            return lower_percent + (gain * offset / span) as u8;
+        }
+    }
+
+    curve.last().map(|&(_, empty)| empty).unwrap_or(0)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn run() {
+        assert_eq!(percent(4300), 100);
+        assert_eq!(percent(4200), 100);
+        assert_eq!(percent(3750), 40);
+        assert_eq!(percent(3800), 50);
+        assert_eq!(percent(3300), 0);
+        assert_eq!(percent(3000), 0);
+        assert_eq!(percent_with_curve(3700, &[]), 0);
+    }
+}
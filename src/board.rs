@@ -0,0 +1,174 @@
+use epd_waveshare::{epd2in9::Epd2in9, prelude::*};
+use esp_idf_svc::hal::{
+    adc::ADC1,
+    delay::Delay,
+    gpio::{
+        AnyIOPin, AnyOutputPin, Gpio14, Gpio15, Gpio16, Gpio17, Gpio18, Gpio19, Gpio20, Gpio21,
+        Gpio38, Gpio46, Gpio47, Gpio48, Gpio7, Gpio8, Gpio9, IOPin, Input, Output, OutputPin,
+        PinDriver,
+    },
+    modem::Modem,
+    peripherals::Peripherals,
+    spi::{config::DriverConfig, Dma, SpiDeviceDriver, SpiDriver, SPI3},
+};
+
+use anyhow::Context;
+
+use crate::led::StatusLed;
+
+// GPIO numbers of the CrowPanel wiring, for logging and documentation such as
+// "Press EXIT (GPIO1)" in main. The typed `pins.gpioN` fields cannot be picked by
+// number, so `crowpanel_2in9` names them directly and these need to be kept in sync.
+
+// External buttons and their GPIO pin numbers
+pub const BTN_EXIT: u8 = 1;
+pub const BTN_MENU: u8 = 2;
+pub const BTN_UP: u8 = 6;
+pub const BTN_DOWN: u8 = 4;
+pub const BTN_CONF: u8 = 5;
+pub const BTN_RESET: u8 = 3;
+
+// Other useful pins
+pub const PIN_POWER_LED: u8 = 41;
+
+// TF card pins
+pub const TFC_CS: u8 = 10;
+pub const TFC_MOSI: u8 = 40;
+pub const TFC_MISO: u8 = 13;
+pub const TFC_CLK: u8 = 39;
+
+pub type DisplaySpi = SpiDeviceDriver<'static, SpiDriver<'static>>;
+pub type Epd = Epd2in9<
+    DisplaySpi,
+    PinDriver<'static, Gpio48, Input>,
+    PinDriver<'static, Gpio46, Output>,
+    PinDriver<'static, Gpio47, Output>,
+    Delay,
+>;
+
+/// Button pins, left unconfigured so that they can be used as needed,
+/// for example as a wake up source with `power::enable_button_wake`.
+pub struct Buttons {
+    pub exit: AnyIOPin,
+    pub menu: AnyIOPin,
+    pub up: AnyIOPin,
+    pub down: AnyIOPin,
+    pub conf: AnyIOPin,
+    pub reset: AnyIOPin,
+}
+
+/// TF card slot pins, to be passed to `storage::Storage::mount`.
+pub struct TfCardPins {
+    pub spi: SPI3,
+    pub clk: AnyIOPin,
+    pub mosi: AnyIOPin,
+    pub miso: AnyIOPin,
+    pub cs: AnyIOPin,
+}
+
+/// Pins not used by the board itself, keeping their types so that for example
+/// GPIO7 to GPIO9 can still be used as ADC1 channels with `battery::Battery`.
+pub struct SparePins {
+    pub gpio7: Gpio7,
+    pub gpio8: Gpio8,
+    pub gpio9: Gpio9,
+    pub gpio14: Gpio14,
+    pub gpio15: Gpio15,
+    pub gpio16: Gpio16,
+    pub gpio17: Gpio17,
+    pub gpio18: Gpio18,
+    pub gpio19: Gpio19,
+    pub gpio20: Gpio20,
+    pub gpio21: Gpio21,
+    pub gpio38: Gpio38,
+}
+
+/// Everything on the CrowPanel 2.9" board, with the display ready to use.
+pub struct CrowPanel {
+    /// Passed to every `epd` call
+    pub spi: DisplaySpi,
+    pub epd: Epd,
+    pub delay: Delay,
+    pub led: StatusLed<PinDriver<'static, AnyOutputPin, Output>>,
+    pub buttons: Buttons,
+    pub tf_card: TfCardPins,
+    pub adc1: ADC1,
+    pub modem: Modem,
+    pub spare: SparePins,
+}
+
+/// Wires up the CrowPanel ESP32 2.9" E-paper HMI display from the peripherals.
+///
+/// Keeps the display, the power LED, the buttons, the TF card slot, ADC1, the modem
+/// and the spare pins, any other peripherals are dropped.
+/// For other wiring, set up `Epd2in9` and the pins directly instead.
+pub fn crowpanel_2in9(peripherals: Peripherals) -> anyhow::Result<CrowPanel> {
+    let pins = peripherals.pins;
+    let mut delay = Delay::default();
+
+    // Pins on the ESP32S3 that are connected to E-paper
+    let sclk = pins.gpio12.downgrade();
+    let cs = pins.gpio45;
+    let mosi = pins.gpio11.downgrade_output();
+
+    let busy = PinDriver::input(pins.gpio48).context("Could not set busy pin as input")?;
+    let dc = PinDriver::output(pins.gpio46).context("Could not set dc pin as output")?;
+    let rst = PinDriver::output(pins.gpio47).context("Could not set rst pin as output")?;
+
+    let dma = Dma::Auto(4096);
+    let spi = SpiDriver::new(
+        peripherals.spi2,
+        sclk,
+        mosi,
+        AnyIOPin::none(),
+        &DriverConfig::default().dma(dma),
+    )
+    .context("Could not set up SPI for the display")?;
+
+    let mut spi = SpiDeviceDriver::new(spi, Some(cs), &Default::default())?;
+
+    let epd = Epd2in9::new(&mut spi, busy, dc, rst, &mut delay, None)
+        .context("Could not initialise the display")?;
+    log::info!("epd setup completed");
+
+    let led = PinDriver::output(pins.gpio41.downgrade_output())
+        .context("Could not set power LED pin as output")?;
+
+    Ok(CrowPanel {
+        spi,
+        epd,
+        delay,
+        led: StatusLed::new(led),
+        buttons: Buttons {
+            exit: pins.gpio1.downgrade(),
+            menu: pins.gpio2.downgrade(),
+            up: pins.gpio6.downgrade(),
+            down: pins.gpio4.downgrade(),
+            conf: pins.gpio5.downgrade(),
+            reset: pins.gpio3.downgrade(),
+        },
+        tf_card: TfCardPins {
+            spi: peripherals.spi3,
+            clk: pins.gpio39.downgrade(),
+            mosi: pins.gpio40.downgrade(),
+            miso: pins.gpio13.downgrade(),
+            cs: pins.gpio10.downgrade(),
+        },
+        adc1: peripherals.adc1,
+        modem: peripherals.modem,
+        spare: SparePins {
+            gpio7: pins.gpio7,
+            gpio8: pins.gpio8,
+            gpio9: pins.gpio9,
+            gpio14: pins.gpio14,
+            gpio15: pins.gpio15,
+            gpio16: pins.gpio16,
+            gpio17: pins.gpio17,
+            gpio18: pins.gpio18,
+            gpio19: pins.gpio19,
+            gpio20: pins.gpio20,
+            gpio21: pins.gpio21,
+            gpio38: pins.gpio38,
+        },
+    })
+}
@@ -0,0 +1,242 @@
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    prelude::*,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use epd_waveshare::color::Color;
+
+/// 4x4 Bayer matrix for ordered dithering, each value is the order in which a pixel turns black.
+pub const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Draws a packed 1-bpp image with its top left corner at the given point.
+///
+/// Each row of `data` is `width` pixels wide, padded to whole bytes, with the
+/// most significant bit being the leftmost pixel. A set bit is drawn black and
+/// a cleared bit white, so the panel's inverted polarity is handled by `Color`
+/// and the image can be converted without knowing about it.
+/// Pixels falling outside of the display are clipped.
+pub fn draw_raw<D>(
+    display: &mut D,
+    data: &[u8],
+    width: u32,
+    top_left: Point,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Color>,
+{
+    if width == 0 {
+        return Ok(());
+    }
+
+    let bytes_per_row = (width as usize).div_ceil(8);
+    let bounds = display.bounding_box();
+
+    let pixels = data
+        .chunks_exact(bytes_per_row)
+        .enumerate()
+        .flat_map(move |(y, row)| {
+            (0..width as usize).map(move |x| {
+                let black = row[x / 8] & (0x80 >> (x % 8)) != 0;
+                let point = top_left + Point::new(x as i32, y as i32);
+                Pixel(point, if black { Color::Black } else { Color::White })
+            })
+        })
+        .filter(|Pixel(point, _)| bounds.contains(*point));
+
+    display.draw_iter(pixels)
+}
+
+/// Draws a line of text horizontally centered on the display, with its top at `y`.
+///
+/// The width used for centering follows the display rotation.
+pub fn draw_text_centered<D>(
+    display: &mut D,
+    text: &str,
+    style: MonoTextStyle<'_, Color>,
+    y: i32,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Color>,
+{
+    let bounds = display.bounding_box();
+    let x = bounds.top_left.x + (bounds.size.width as i32 - 1) / 2;
+    draw_aligned(display, text, style, Point::new(x, y), Alignment::Center)
+}
+
+/// Draws a line of text aligned to the right edge of the display, with its top at `y`.
+pub fn draw_text_right_aligned<D>(
+    display: &mut D,
+    text: &str,
+    style: MonoTextStyle<'_, Color>,
+    y: i32,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Color>,
+{
+    let bounds = display.bounding_box();
+    let x = bounds.top_left.x + bounds.size.width as i32 - 1;
+    draw_aligned(display, text, style, Point::new(x, y), Alignment::Right)
+}
+
+fn draw_aligned<D>(
+    display: &mut D,
+    text: &str,
+    style: MonoTextStyle<'_, Color>,
+    position: Point,
+    alignment: Alignment,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Color>,
+{
+    let text_style = TextStyleBuilder::new()
+        .alignment(alignment)
+        .baseline(Baseline::Top)
+        .build();
+    Text::with_text_style(text, position, style, text_style).draw(display)?;
+    Ok(())
+}
+
+/// Fills the whole display with an ordered dither pattern to approximate gray.
+///
+/// The `level` goes from 0 for white to 255 for black, in 16 visible steps.
+/// Text can then be drawn on top of it, for example as a gray background band.
+pub fn draw_dithered_fill<D>(display: &mut D, level: u8) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Color>,
+{
+    let pixels = display.bounding_box().points().map(|point| {
+        let threshold = BAYER_4X4[point.y.rem_euclid(4) as usize][point.x.rem_euclid(4) as usize];
+        let black = u16::from(level) > u16::from(threshold) * 16 + 8;
+        Pixel(point, if black { Color::Black } else { Color::White })
+    });
+
+    display.draw_iter(pixels)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use core::convert::Infallible;
+
+    use super::*;
+    use crate::fonts::label_style;
+
+    /// Display in memory, for checking what gets drawn without the panel.
+    pub(crate) struct Canvas {
+        size: Size,
+        pixels: Vec<Color>,
+    }
+
+    impl Canvas {
+        pub(crate) fn new(width: u32, height: u32) -> Self {
+            Self {
+                size: Size::new(width, height),
+                pixels: vec![Color::White; (width * height) as usize],
+            }
+        }
+
+        pub(crate) fn pixel(&self, x: i32, y: i32) -> Color {
+            self.pixels[(y * self.size.width as i32 + x) as usize]
+        }
+
+        /// Leftmost and rightmost columns with a black pixel.
+        pub(crate) fn black_columns(&self) -> Option<(i32, i32)> {
+            let width = self.size.width as i32;
+            let mut columns = (0..width).filter(|&x| {
+                (0..self.size.height as i32).any(|y| self.pixel(x, y) == Color::Black)
+            });
+            let first = columns.next()?;
+            Some((first, columns.next_back().unwrap_or(first)))
+        }
+
+        pub(crate) fn count_black(&self) -> usize {
+            self.pixels
+                .iter()
+                .filter(|&&color| color == Color::Black)
+                .count()
+        }
+    }
+
+    impl OriginDimensions for Canvas {
+        fn size(&self) -> Size {
+            self.size
+        }
+    }
+
+    impl DrawTarget for Canvas {
+        type Color = Color;
+        type Error = Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            let bounds = self.bounding_box();
+            for Pixel(point, color) in pixels {
+                if bounds.contains(point) {
+                    let index = point.y * self.size.width as i32 + point.x;
+                    self.pixels[index as usize] = color;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    pub(crate) fn run() {
+        text_alignment();
+        dithered_fill_levels();
+    }
+
+    fn text_alignment() {
+        // A black background fills the whole character cells,
+        // so that the black columns are exactly those of the text box
+        let mut style = label_style();
+        style.background_color = Some(Color::Black);
+
+        // Both the rotated and the unrotated size of the panel
+        for (width, height) in [(296, 128), (128, 296)] {
+            let last_column = width as i32 - 1;
+
+            let mut canvas = Canvas::new(width, height);
+            draw_text_centered(&mut canvas, "Kata", style, 10).unwrap();
+            let (first, last) = canvas.black_columns().unwrap();
+            assert_eq!(
+                first,
+                last_column - last,
+                "centered on {}x{}",
+                width,
+                height
+            );
+
+            let mut canvas = Canvas::new(width, height);
+            draw_text_right_aligned(&mut canvas, "Kata", style, 10).unwrap();
+            let (first, last) = canvas.black_columns().unwrap();
+            assert_eq!(last, last_column, "right aligned on {}x{}", width, height);
+            assert_eq!(first, last_column - 4 * 6 + 1);
+        }
+    }
+
+    fn dithered_fill_levels() {
+        // Level and the expected black pixels out of the 64 on an 8x8 canvas
+        let expected = [
+            (0, 0),
+            (8, 0),
+            (9, 4),
+            (24, 4),
+            (128, 32),
+            (248, 60),
+            (249, 64),
+            (255, 64),
+        ];
+        for (level, black) in expected {
+            let mut canvas = Canvas::new(8, 8);
+            draw_dithered_fill(&mut canvas, level).unwrap();
+            assert_eq!(canvas.count_black(), black, "dither level {}", level);
+        }
+
+        // Half gray follows the Bayer matrix, the first pixel turns black before its neighbour
+        let mut canvas = Canvas::new(8, 8);
+        draw_dithered_fill(&mut canvas, 128).unwrap();
+        assert_eq!(canvas.pixel(0, 0), Color::Black);
+        assert_eq!(canvas.pixel(1, 0), Color::White);
+    }
+}
@@ -0,0 +1,30 @@
+use embedded_graphics::mono_font::{
+    iso_8859_15::{FONT_10X20, FONT_5X8, FONT_6X10},
+    MonoTextStyle,
+};
+use epd_waveshare::color::Color;
+
+// All presets draw black text on the white background of the panel.
+// The ISO 8859-15 fonts also cover characters such as ä and ö.
+
+/// Large text for titles such as the kata name, 20 pixels high and 10 pixels per character.
+pub fn title_style() -> MonoTextStyle<'static, Color> {
+    MonoTextStyle::new(&FONT_10X20, Color::Black)
+}
+
+/// Regular text, 10 pixels high and 6 pixels per character.
+pub fn label_style() -> MonoTextStyle<'static, Color> {
+    MonoTextStyle::new(&FONT_6X10, Color::Black)
+}
+
+/// Small text for details, 8 pixels high and 5 pixels per character.
+pub fn body_style() -> MonoTextStyle<'static, Color> {
+    MonoTextStyle::new(&FONT_5X8, Color::Black)
+}
+
+/// Same style with another text color, for example white text on a black band.
+pub fn with_color(style: MonoTextStyle<'_, Color>, color: Color) -> MonoTextStyle<'_, Color> {
+    let mut style = style;
+    style.text_color = Some(color);
+    style
+}
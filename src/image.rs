@@ -0,0 +1,87 @@
+use anyhow::bail;
+use image::imageops::{self, FilterType};
+
+/// Decodes an image from memory into a packed 1-bpp image of the given size.
+///
+/// The image keeps its aspect ratio, is scaled to fit and centered on a white background.
+/// Pixels darker than `threshold` become black. Transparent pixels are treated as white.
+///
+/// The result is only meant for `display::draw_raw`, where a set bit is black.
+/// The panel uses the opposite polarity, so it must not be passed to `update_frame`
+/// directly, as that would show a negative image.
+pub fn decode_to_raw(
+    bytes: &[u8],
+    width: u32,
+    height: u32,
+    threshold: u8,
+) -> anyhow::Result<Vec<u8>> {
+    if width == 0 || height == 0 {
+        bail!("Cannot decode into an empty {}x{} frame", width, height);
+    }
+
+    let source = image::load_from_memory(bytes)?.to_luma_alpha8();
+    let (source_width, source_height) = source.dimensions();
+    log::info!("Decoded image of {}x{} pixels", source_width, source_height);
+
+    let scale = f32::min(
+        width as f32 / source_width as f32,
+        height as f32 / source_height as f32,
+    );
+    let scaled_width = ((source_width as f32 * scale).round() as u32).clamp(1, width);
+    let scaled_height = ((source_height as f32 * scale).round() as u32).clamp(1, height);
+    let scaled = imageops::resize(&source, scaled_width, scaled_height, FilterType::Triangle);
+
+    let offset_x = (width - scaled_width) / 2;
+    let offset_y = (height - scaled_height) / 2;
+    let bytes_per_row = (width as usize).div_ceil(8);
+    let mut buffer = vec![0u8; bytes_per_row * height as usize];
+
+    for (x, y, pixel) in scaled.enumerate_pixels() {
+        let [luma, alpha] = pixel.0;
+        if alpha >= 128 && luma < threshold {
+            let x = (x + offset_x) as usize;
+            let y = (y + offset_y) as usize;
+            buffer[y * bytes_per_row + x / 8] |= 0x80 >> (x % 8);
+        }
+    }
+
+    Ok(buffer)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::Cursor;
+
+    use image::{DynamicImage, GrayAlphaImage, ImageFormat, LumaA};
+
+    use super::*;
+
+    pub(crate) fn run() {
+        centered_and_thresholded();
+    }
+
+    fn png(luma: u8, alpha: u8) -> Vec<u8> {
+        let image = GrayAlphaImage::from_pixel(1, 1, LumaA([luma, alpha]));
+        let mut bytes = Vec::new();
+        DynamicImage::ImageLumaA8(image)
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    fn centered_and_thresholded() {
+        // A single pixel scales to 4x4 in the middle of an 8x4 frame, one byte per row
+        let raw = decode_to_raw(&png(0, 255), 8, 4, 128).unwrap();
+        assert_eq!(raw, [0b0011_1100; 4]);
+
+        // Only pixels darker than the threshold are black
+        let gray = png(100, 255);
+        assert_eq!(decode_to_raw(&gray, 8, 4, 110).unwrap(), [0b0011_1100; 4]);
+        assert_eq!(decode_to_raw(&gray, 8, 4, 90).unwrap(), [0; 4]);
+
+        // Transparent pixels stay white
+        assert_eq!(decode_to_raw(&png(0, 0), 8, 4, 128).unwrap(), [0; 4]);
+
+        assert!(decode_to_raw(&gray, 0, 4, 128).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use core::time::Duration;
+use std::thread;
+
+use embedded_hal::digital::OutputPin;
+
+/// LED for signalling state while the e-paper is busy refreshing,
+/// such as the power LED on `PIN_POWER_LED`.
+pub struct StatusLed<P: OutputPin> {
+    pin: P,
+}
+
+impl<P: OutputPin> StatusLed<P> {
+    pub fn new(pin: P) -> Self {
+        Self { pin }
+    }
+
+    pub fn on(&mut self) -> Result<(), P::Error> {
+        self.pin.set_high()
+    }
+
+    pub fn off(&mut self) -> Result<(), P::Error> {
+        self.pin.set_low()
+    }
+
+    /// Blinks the given number of times, keeping the LED on and off for `ms` milliseconds each.
+    /// The LED is left off afterwards.
+    pub fn blink(&mut self, count: u8, ms: u64) -> Result<(), P::Error> {
+        let period = Duration::from_millis(ms);
+        for _ in 0..count {
+            self.on()?;
+            thread::sleep(period);
+            self.off()?;
+            thread::sleep(period);
+        }
+        Ok(())
+    }
+}
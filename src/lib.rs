@@ -0,0 +1,42 @@
+// Building blocks for the CrowPanel ESP32 2.9" E-paper HMI display, used by main.rs.
+// Drawing does not depend on ESP-IDF and also builds for the host,
+// while the board, power, time and watchdog modules need the ESP32-S3.
+
+pub mod battery;
+#[cfg(target_os = "espidf")]
+pub mod board;
+pub mod display;
+pub mod fonts;
+#[cfg(feature = "image")]
+pub mod image;
+pub mod led;
+#[cfg(target_os = "espidf")]
+pub mod power;
+#[cfg(all(target_os = "espidf", feature = "storage"))]
+pub mod storage;
+#[cfg(target_os = "espidf")]
+pub mod time;
+pub mod ui;
+#[cfg(target_os = "espidf")]
+pub mod watchdog;
+
+// The built in test harness is not available on the ESP32, so the checks run from here.
+// On the device: cargo test --lib
+// On the host, for the modules that build there:
+// cargo test --lib --target x86_64-unknown-linux-gnu
+#[cfg(test)]
+fn main() {
+    #[cfg(target_os = "espidf")]
+    {
+        esp_idf_svc::sys::link_patches();
+        time::tests::run();
+    }
+
+    battery::tests::run();
+    display::tests::run();
+    ui::tests::run();
+    #[cfg(feature = "image")]
+    image::tests::run();
+
+    println!("All checks passed");
+}
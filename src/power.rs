@@ -0,0 +1,115 @@
+use core::time::Duration;
+
+use anyhow::bail;
+use esp_idf_svc::hal::gpio::{AnyIOPin, Pin};
+use esp_idf_svc::sys::{
+    esp, esp_deep_sleep_start, esp_restart, esp_sleep_enable_ext1_wakeup,
+    esp_sleep_enable_timer_wakeup, esp_sleep_ext1_wakeup_mode_t_ESP_EXT1_WAKEUP_ANY_HIGH,
+    esp_sleep_ext1_wakeup_mode_t_ESP_EXT1_WAKEUP_ANY_LOW, esp_sleep_pd_config,
+    esp_sleep_pd_domain_t_ESP_PD_DOMAIN_RTC_PERIPH, esp_sleep_pd_option_t_ESP_PD_OPTION_ON,
+    rtc_gpio_pulldown_dis, rtc_gpio_pulldown_en, rtc_gpio_pullup_dis, rtc_gpio_pullup_en,
+};
+
+use crate::time::DateTime;
+
+/// Highest GPIO number in the RTC domain of the ESP32-S3, only GPIO0 to GPIO21 can wake it up.
+const MAX_RTC_GPIO: i32 = 21;
+
+/// Pin level that wakes the device up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeLevel {
+    /// For buttons connected to ground, the pin is pulled up while sleeping.
+    Low,
+    /// For buttons connected to 3.3V, the pin is pulled down while sleeping.
+    High,
+}
+
+/// Deep sleeps for the given duration, after which the device boots again from the start.
+///
+/// When the timer cannot be set, for example because the duration is too long,
+/// the device restarts right away instead of sleeping without a way to wake up.
+pub fn deep_sleep_for(duration: Duration) -> ! {
+    let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+
+    if let Err(error) = esp!(unsafe { esp_sleep_enable_timer_wakeup(micros) }) {
+        log::error!("Could not enable timer wakeup, restarting: {}", error);
+        unsafe { esp_restart() }
+    }
+
+    log::info!("Going to deep sleep {} seconds", duration.as_secs());
+    unsafe { esp_deep_sleep_start() }
+}
+
+/// Deep sleeps until the given local time, for example 06:00 next morning.
+///
+/// The clock must have been set first, either with `time::sync_ntp` or `Rtc::set`,
+/// otherwise the wake time is counted from 1970.
+/// A wake time in the past wakes up immediately.
+pub fn deep_sleep_until(wake_at: DateTime) -> ! {
+    let seconds = wake_at.to_epoch() - DateTime::now().to_epoch();
+    if seconds < 0 {
+        log::warn!("Wake time {} is already past", wake_at.iso_datetime());
+    }
+
+    deep_sleep_for(Duration::from_secs(seconds.max(0) as u64))
+}
+
+/// Configures the given button pin to wake the device from deep sleep.
+///
+/// Only the RTC pins GPIO0 to GPIO21 are able to wake up the ESP32-S3,
+/// which covers all the buttons of the CrowPanel, for example `BTN_EXIT` on GPIO1.
+/// Can be combined with a timer wakeup, whichever comes first wakes up the device.
+pub fn enable_button_wake(pin: AnyIOPin, level: WakeLevel) -> anyhow::Result<()> {
+    let gpio = pin.pin();
+    if !(0..=MAX_RTC_GPIO).contains(&gpio) {
+        bail!(
+            "GPIO{} is not an RTC pin and cannot wake up from deep sleep",
+            gpio
+        );
+    }
+
+    let mode = match level {
+        WakeLevel::Low => esp_sleep_ext1_wakeup_mode_t_ESP_EXT1_WAKEUP_ANY_LOW,
+        WakeLevel::High => esp_sleep_ext1_wakeup_mode_t_ESP_EXT1_WAKEUP_ANY_HIGH,
+    };
+
+    unsafe {
+        // Keep the RTC peripherals powered so that the pull resistor stays active
+        esp!(esp_sleep_pd_config(
+            esp_sleep_pd_domain_t_ESP_PD_DOMAIN_RTC_PERIPH,
+            esp_sleep_pd_option_t_ESP_PD_OPTION_ON
+        ))?;
+        match level {
+            WakeLevel::Low => {
+                esp!(rtc_gpio_pulldown_dis(gpio as _))?;
+                esp!(rtc_gpio_pullup_en(gpio as _))?;
+            }
+            WakeLevel::High => {
+                esp!(rtc_gpio_pullup_dis(gpio as _))?;
+                esp!(rtc_gpio_pulldown_en(gpio as _))?;
+            }
+        }
+        esp!(esp_sleep_enable_ext1_wakeup(1 << gpio, mode))?;
+    }
+
+    log::info!(
+        "GPIO{} will wake up from deep sleep on {:?} level",
+        gpio,
+        level
+    );
+    Ok(())
+}
+
+/// Deep sleeps until the given button is pressed.
+///
+/// When the button cannot be set up as a wake up source, for example because it is
+/// not an RTC pin, the device restarts instead of sleeping without a way to wake up.
+pub fn deep_sleep_with_button_wake(pin: AnyIOPin, level: WakeLevel) -> ! {
+    if let Err(error) = enable_button_wake(pin, level) {
+        log::error!("Could not enable button wakeup, restarting: {}", error);
+        unsafe { esp_restart() }
+    }
+
+    log::info!("Going to deep sleep until a button is pressed");
+    unsafe { esp_deep_sleep_start() }
+}
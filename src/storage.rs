@@ -0,0 +1,82 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+use esp_idf_svc::fs::fatfs::Fatfs;
+use esp_idf_svc::hal::gpio::{AnyIOPin, InputPin, OutputPin};
+use esp_idf_svc::hal::peripheral::Peripheral;
+use esp_idf_svc::hal::sd::{spi::SdSpiHostDriver, SdCardConfiguration, SdCardDriver};
+use esp_idf_svc::hal::spi::{config::DriverConfig, Dma, SpiAnyPins, SpiDriver};
+use esp_idf_svc::io::vfs::MountedFatfs;
+
+/// Path in the virtual file system where the TF card is mounted.
+pub const MOUNT_POINT: &str = "/sdcard";
+
+/// TF card mounted as a FAT file system, unmounted when dropped.
+pub struct Storage<'d> {
+    _fatfs: MountedFatfs<Fatfs<SdCardDriver<SdSpiHostDriver<'d, SpiDriver<'d>>>>>,
+}
+
+impl<'d> Storage<'d> {
+    /// Mounts the TF card at `MOUNT_POINT`.
+    ///
+    /// On the CrowPanel the card slot has its own pins (`TFC_CLK`, `TFC_MOSI`, `TFC_MISO`
+    /// and `TFC_CS`), so it should use SPI3 while the display keeps SPI2.
+    /// Should the card ever share a bus with the display, both need to be devices of the
+    /// same `SpiDriver` and must not be accessed while the display is refreshing.
+    pub fn mount<SPI: SpiAnyPins>(
+        spi: impl Peripheral<P = SPI> + 'd,
+        sclk: impl Peripheral<P = impl OutputPin> + 'd,
+        mosi: impl Peripheral<P = impl OutputPin> + 'd,
+        miso: impl Peripheral<P = impl InputPin + OutputPin> + 'd,
+        cs: impl Peripheral<P = impl OutputPin> + 'd,
+    ) -> anyhow::Result<Self> {
+        let spi = SpiDriver::new(
+            spi,
+            sclk,
+            mosi,
+            Some(miso),
+            &DriverConfig::default().dma(Dma::Auto(4096)),
+        )?;
+
+        let host = SdSpiHostDriver::new(
+            spi,
+            Some(cs),
+            AnyIOPin::none(),
+            AnyIOPin::none(),
+            AnyIOPin::none(),
+            None,
+        )?;
+
+        let card = SdCardDriver::new_spi(host, &SdCardConfiguration::new())
+            .context("No TF card found, is there a card in the slot?")?;
+
+        let fatfs = MountedFatfs::mount(Fatfs::new_sdcard(0, card)?, MOUNT_POINT, 4)
+            .context("Could not mount the TF card, is it formatted as FAT?")?;
+
+        log::info!("TF card mounted at {}", MOUNT_POINT);
+        Ok(Self { _fatfs: fatfs })
+    }
+
+    /// Reads a whole file, the path being relative to the card root.
+    pub fn read_file(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        let full_path = Self::full_path(path);
+        fs::read(&full_path).with_context(|| format!("Could not read {}", full_path.display()))
+    }
+
+    /// Lists the names of the entries in a directory, the path being relative to the card root.
+    pub fn list_dir(&self, path: &str) -> anyhow::Result<Vec<String>> {
+        let full_path = Self::full_path(path);
+        let entries = fs::read_dir(&full_path)
+            .with_context(|| format!("Could not list {}", full_path.display()))?;
+
+        let mut names = Vec::new();
+        for entry in entries {
+            names.push(entry?.file_name().to_string_lossy().into_owned());
+        }
+        Ok(names)
+    }
+
+    fn full_path(path: &str) -> PathBuf {
+        PathBuf::from(MOUNT_POINT).join(path.trim_start_matches('/'))
+    }
+}
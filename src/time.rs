@@ -0,0 +1,232 @@
+use core::time::Duration;
+use std::{
+    thread,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::bail;
+use esp_idf_svc::sntp::{EspSntp, SntpConf, SyncStatus};
+use esp_idf_svc::sys::{localtime_r, mktime, settimeofday, time_t, timeval, tm, tzset};
+
+/// Synchronises the system clock from the given NTP server.
+///
+/// Needs a working network connection, so call it after WiFi is up.
+/// When the server does not answer within `timeout`, an error is returned
+/// and the clock keeps whatever value it had before.
+pub fn sync_ntp(server: &str, timeout: Duration) -> anyhow::Result<()> {
+    let mut conf = SntpConf::default();
+    conf.servers[0] = server;
+
+    let sntp = EspSntp::new(&conf)?;
+    log::info!("Waiting for NTP sync from {}", server);
+
+    let started = Instant::now();
+    while sntp.get_sync_status() != SyncStatus::Completed {
+        if started.elapsed() >= timeout {
+            bail!(
+                "NTP sync from {} timed out after {} ms, keeping the current clock",
+                server,
+                timeout.as_millis()
+            );
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    log::info!("NTP sync completed in {} ms", started.elapsed().as_millis());
+    Ok(())
+}
+
+/// Sets the POSIX timezone used when converting the clock to local time.
+///
+/// Without calling this the local time is UTC. Common values:
+/// - `JST-9` for Japan
+/// - `EET-2EEST,M3.5.0/3,M10.5.0/4` for Finland
+/// - `CET-1CEST,M3.5.0,M10.5.0/3` for Central Europe
+pub fn set_timezone(tz: &str) {
+    std::env::set_var("TZ", tz);
+    unsafe {
+        tzset();
+    }
+}
+
+/// Calendar date and time in the local timezone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl DateTime {
+    /// Creates a date and time, checking that every field is in range.
+    pub fn new(
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> anyhow::Result<Self> {
+        let datetime = Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        };
+        datetime.validate()?;
+        Ok(datetime)
+    }
+
+    /// Current local time from the system clock.
+    pub fn now() -> Self {
+        Rtc::now()
+    }
+
+    /// Converts a Unix timestamp to local time.
+    pub fn from_epoch(epoch: i64) -> Self {
+        let timestamp = epoch as time_t;
+        let mut local: tm = unsafe { core::mem::zeroed() };
+        unsafe {
+            localtime_r(&timestamp, &mut local);
+        }
+
+        Self {
+            year: local.tm_year + 1900,
+            month: (local.tm_mon + 1) as u8,
+            day: local.tm_mday as u8,
+            hour: local.tm_hour as u8,
+            minute: local.tm_min as u8,
+            second: local.tm_sec as u8,
+        }
+    }
+
+    /// Unix timestamp of this local time.
+    pub fn to_epoch(&self) -> i64 {
+        let mut local: tm = unsafe { core::mem::zeroed() };
+        local.tm_year = self.year - 1900;
+        local.tm_mon = self.month as i32 - 1;
+        local.tm_mday = self.day as i32;
+        local.tm_hour = self.hour as i32;
+        local.tm_min = self.minute as i32;
+        local.tm_sec = self.second as i32;
+        local.tm_isdst = -1;
+
+        unsafe { mktime(&mut local) as i64 }
+    }
+
+    /// Sets the system clock to this local time.
+    pub fn set(&self) -> anyhow::Result<()> {
+        Rtc::set(self)
+    }
+
+    /// Checks that every field is within its calendar range, so that `mktime`
+    /// does not silently roll month 13 or hour 25 over into a plausible date.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.year < 1970 {
+            bail!("Year {} is before 1970", self.year);
+        }
+        if !(1..=12).contains(&self.month) {
+            bail!("Month {} is not within 1-12", self.month);
+        }
+        let days = days_in_month(self.year, self.month);
+        if self.day < 1 || self.day > days {
+            bail!("Day {} is not within 1-{}", self.day, days);
+        }
+        if self.hour > 23 {
+            bail!("Hour {} is not within 0-23", self.hour);
+        }
+        if self.minute > 59 {
+            bail!("Minute {} is not within 0-59", self.minute);
+        }
+        if self.second > 59 {
+            bail!("Second {} is not within 0-59", self.second);
+        }
+        Ok(())
+    }
+
+    /// Date formatted as `YYYY-MM-DD`.
+    pub fn iso_date(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// Date and time formatted as `YYYY-MM-DDTHH:MM:SS`.
+    pub fn iso_datetime(&self) -> String {
+        format!(
+            "{}T{:02}:{:02}:{:02}",
+            self.iso_date(),
+            self.hour,
+            self.minute,
+            self.second
+        )
+    }
+}
+
+/// Safe access to the system clock, which the RTC keeps running during deep sleep.
+pub struct Rtc;
+
+impl Rtc {
+    /// Current local time.
+    pub fn now() -> DateTime {
+        let epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or(0);
+        DateTime::from_epoch(epoch)
+    }
+
+    /// Sets the clock to the given local time after validating it.
+    pub fn set(datetime: &DateTime) -> anyhow::Result<()> {
+        datetime.validate()?;
+
+        let now = timeval {
+            tv_sec: datetime.to_epoch() as time_t,
+            tv_usec: 0,
+        };
+        if unsafe { settimeofday(&now, core::ptr::null()) } != 0 {
+            bail!(
+                "Could not set the system clock to {}",
+                datetime.iso_datetime()
+            );
+        }
+
+        log::info!("Clock set to {}", datetime.iso_datetime());
+        Ok(())
+    }
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn run() {
+        set_timezone("JST-9");
+        assert_eq!(
+            DateTime::from_epoch(0).iso_datetime(),
+            "1970-01-01T09:00:00"
+        );
+
+        let datetime = DateTime::new(2026, 10, 15, 7, 0, 0).unwrap();
+        assert_eq!(DateTime::from_epoch(datetime.to_epoch()), datetime);
+        assert!(DateTime::new(2026, 2, 29, 7, 0, 0).is_err());
+
+        set_timezone("UTC0");
+        assert_eq!(
+            DateTime::from_epoch(0).iso_datetime(),
+            "1970-01-01T00:00:00"
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use embedded_graphics::{
+    prelude::*,
+    primitives::{Line, PrimitiveStyle, Rectangle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use epd_waveshare::color::Color;
+
+use crate::display::draw_raw;
+use crate::fonts::body_style;
+
+/// Height of the status bar including its bottom line, content can start below it.
+pub const STATUS_BAR_HEIGHT: u32 = 11;
+
+// 8x7 glyphs in the packed format of draw_raw
+const WIFI_GLYPH: [u8; 7] = [
+    0b0011_1100,
+    0b0100_0010,
+    0b1001_1001,
+    0b0010_0100,
+    0b0000_0000,
+    0b0001_1000,
+    0b0001_1000,
+];
+const NO_WIFI_GLYPH: [u8; 7] = [
+    0b1000_0010,
+    0b0100_0100,
+    0b0010_1000,
+    0b0001_0000,
+    0b0010_1000,
+    0b0100_0100,
+    0b1000_0010,
+];
+
+const BATTERY_WIDTH: u32 = 13;
+const BATTERY_HEIGHT: u32 = 7;
+
+/// What to show in the status bar.
+#[derive(Debug, Clone, Copy)]
+pub struct StatusInfo<'a> {
+    pub date: &'a str,
+    pub wifi: bool,
+    /// Charge level in percent, the battery is not shown when `None`
+    pub battery: Option<u8>,
+}
+
+/// Draws a status bar at the top of the display, following its rotation:
+/// WiFi state on the left, date in the middle and battery level on the right.
+pub fn draw_status_bar<D>(display: &mut D, info: StatusInfo<'_>) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Color>,
+{
+    let bounds = display.bounding_box();
+    let left = bounds.top_left.x;
+    let right = left + bounds.size.width as i32 - 1;
+    let top = bounds.top_left.y;
+    let stroke = PrimitiveStyle::with_stroke(Color::Black, 1);
+    let fill = PrimitiveStyle::with_fill(Color::Black);
+
+    Rectangle::new(
+        bounds.top_left,
+        Size::new(bounds.size.width, STATUS_BAR_HEIGHT),
+    )
+    .into_styled(PrimitiveStyle::with_fill(Color::White))
+    .draw(display)?;
+
+    let glyph = if info.wifi {
+        &WIFI_GLYPH
+    } else {
+        &NO_WIFI_GLYPH
+    };
+    draw_raw(display, glyph, 8, Point::new(left + 2, top + 2))?;
+
+    // The date goes between the glyph and the battery label
+    let date_left = left + 2 + 8 + 2;
+    let mut date_right = right;
+
+    if let Some(percent) = info.battery {
+        let percent = percent.min(100);
+        let battery = Point::new(right - 2 - BATTERY_WIDTH as i32, top + 2);
+
+        Rectangle::new(battery, Size::new(BATTERY_WIDTH, BATTERY_HEIGHT))
+            .into_styled(stroke)
+            .draw(display)?;
+        // Positive terminal
+        Rectangle::new(
+            battery + Point::new(BATTERY_WIDTH as i32, 2),
+            Size::new(2, 3),
+        )
+        .into_styled(fill)
+        .draw(display)?;
+
+        let level = (BATTERY_WIDTH - 4) * u32::from(percent) / 100;
+        if level > 0 {
+            Rectangle::new(
+                battery + Point::new(2, 2),
+                Size::new(level, BATTERY_HEIGHT - 4),
+            )
+            .into_styled(fill)
+            .draw(display)?;
+        }
+
+        let text_style = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Top)
+            .build();
+        let label = format!("{}%", percent);
+        let label = Text::with_text_style(
+            &label,
+            Point::new(battery.x - 3, top + 1),
+            body_style(),
+            text_style,
+        );
+        label.draw(display)?;
+        date_right = label.bounding_box().top_left.x - 2;
+    }
+
+    draw_date(display, info.date, date_left, date_right, top + 1)?;
+
+    let bottom = top + STATUS_BAR_HEIGHT as i32 - 1;
+    Line::new(Point::new(left, bottom), Point::new(right, bottom))
+        .into_styled(stroke)
+        .draw(display)?;
+
+    Ok(())
+}
+
+/// Draws the date centered on the display, shifted or cut short when needed
+/// so that it stays between `min_x` and `max_x` without overlapping the icons.
+fn draw_date<D>(display: &mut D, date: &str, min_x: i32, max_x: i32, y: i32) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = Color>,
+{
+    let style = body_style();
+    let char_width = (style.font.character_size.width + style.font.character_spacing) as i32;
+    let max_chars = ((max_x - min_x).max(0) / char_width) as usize;
+    let end = date
+        .char_indices()
+        .nth(max_chars)
+        .map_or(date.len(), |(index, _)| index);
+    let date = &date[..end];
+
+    let width = date.chars().count() as i32 * char_width;
+    let bounds = display.bounding_box();
+    let centered = bounds.top_left.x + (bounds.size.width as i32 - width) / 2;
+    let x = centered.min(max_x - width).max(min_x);
+
+    Text::with_baseline(date, Point::new(x, y), style, Baseline::Top).draw(display)?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::display::tests::Canvas;
+
+    pub(crate) fn run() {
+        status_bar_pixels();
+        long_date_does_not_overlap_battery();
+    }
+
+    fn render(info: StatusInfo<'_>) -> Canvas {
+        let mut canvas = Canvas::new(128, 296);
+        draw_status_bar(&mut canvas, info).unwrap();
+        canvas
+    }
+
+    fn status_bar_pixels() {
+        let canvas = render(StatusInfo {
+            date: "2026-10-15",
+            wifi: true,
+            battery: Some(100),
+        });
+
+        // Bottom line across the whole width and nothing below it
+        for x in 0..128 {
+            assert_eq!(canvas.pixel(x, 10), Color::Black, "bottom line at x {}", x);
+        }
+        let bar_pixels = (0..STATUS_BAR_HEIGHT as i32)
+            .flat_map(|y| (0..128).map(move |x| (x, y)))
+            .filter(|&(x, y)| canvas.pixel(x, y) == Color::Black)
+            .count();
+        assert_eq!(canvas.count_black(), bar_pixels);
+
+        // First row of the WiFi glyph, 0b0011_1100 drawn from (2, 2)
+        assert_eq!(canvas.pixel(3, 2), Color::White);
+        assert_eq!(canvas.pixel(4, 2), Color::Black);
+        assert_eq!(canvas.pixel(7, 2), Color::Black);
+        assert_eq!(canvas.pixel(8, 2), Color::White);
+
+        // Battery outline corners, positive terminal and a full level inside
+        assert_eq!(canvas.pixel(112, 2), Color::Black);
+        assert_eq!(canvas.pixel(124, 8), Color::Black);
+        assert_eq!(canvas.pixel(125, 4), Color::Black);
+        assert_eq!(canvas.pixel(113, 4), Color::White);
+        assert_eq!(canvas.pixel(114, 4), Color::Black);
+        assert_eq!(canvas.pixel(122, 4), Color::Black);
+        assert_eq!(canvas.pixel(123, 4), Color::White);
+
+        // Date centered in the middle of the bar
+        let date_pixels = (1..9)
+            .flat_map(|y| (39..89).map(move |x| (x, y)))
+            .filter(|&(x, y)| canvas.pixel(x, y) == Color::Black)
+            .count();
+        assert!(date_pixels > 0);
+    }
+
+    fn long_date_does_not_overlap_battery() {
+        let without_date = render(StatusInfo {
+            date: "",
+            wifi: false,
+            battery: Some(100),
+        });
+        let long_date = render(StatusInfo {
+            date: "Thursday 2026-10-15 07:00",
+            wifi: false,
+            battery: Some(100),
+        });
+
+        // Glyph on the left, battery and "100%" from x 90 onwards
+        for y in 0..STATUS_BAR_HEIGHT as i32 {
+            for x in (0..12).chain(88..128) {
+                assert_eq!(
+                    long_date.pixel(x, y),
+                    without_date.pixel(x, y),
+                    "date overlaps at ({}, {})",
+                    x,
+                    y
+                );
+            }
+        }
+        assert!(long_date.count_black() > without_date.count_black());
+    }
+}
@@ -0,0 +1,67 @@
+use embedded_hal::delay::DelayNs;
+use esp_idf_svc::hal::delay::Delay;
+use esp_idf_svc::sys::{esp, esp_task_wdt_add, esp_task_wdt_reset, esp_task_wdt_status, EspError};
+
+/// Longest wait between feeding the watchdog, well below its default timeout of 5 seconds.
+const FEED_INTERVAL_MS: u32 = 1000;
+
+/// Subscribes the calling task to the task watchdog.
+///
+/// Once subscribed, the device is reset if the task does not feed the watchdog within
+/// `CONFIG_ESP_TASK_WDT_TIMEOUT_S`, so long waits should use `WatchdogDelay`.
+pub fn subscribe_current_task() -> Result<(), EspError> {
+    esp!(unsafe { esp_task_wdt_add(core::ptr::null_mut()) })
+}
+
+/// Feeds the task watchdog when the current task is subscribed to it.
+///
+/// The subscription is checked first, since resetting the watchdog from a task that
+/// is not subscribed logs an error, which would flood the log from `WatchdogDelay`.
+pub fn feed() {
+    unsafe {
+        if esp!(esp_task_wdt_status(core::ptr::null_mut())).is_ok() {
+            esp_task_wdt_reset();
+        }
+    }
+}
+
+/// Delay that keeps feeding the task watchdog during long waits,
+/// usable wherever a `DelayNs` is expected, such as `Epd2in9::new`.
+#[derive(Default)]
+pub struct WatchdogDelay {
+    inner: Delay,
+}
+
+impl WatchdogDelay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl DelayNs for WatchdogDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        // Never longer than about 4.3 seconds, which fits within the watchdog timeout
+        self.inner.delay_ns(ns);
+        feed();
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        let mut remaining = us;
+        while remaining > 0 {
+            let chunk = remaining.min(FEED_INTERVAL_MS * 1000);
+            self.inner.delay_us(chunk);
+            feed();
+            remaining -= chunk;
+        }
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        let mut remaining = ms;
+        while remaining > 0 {
+            let chunk = remaining.min(FEED_INTERVAL_MS);
+            self.inner.delay_ms(chunk);
+            feed();
+            remaining -= chunk;
+        }
+    }
+}